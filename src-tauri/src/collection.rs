@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// A single generated piece, kept alongside the intent that produced it so
+/// a later session can re-open it without re-running generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationRecord {
+    pub id: String,
+    pub created_at: String,
+    pub intent: crate::commands::EmotionalIntent,
+    pub result: serde_json::Value,
+}
+
+/// The full set of generations persisted for a user. This is the unit the
+/// `Database` trait reads and writes in one shot; callers never see a
+/// partial write.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Collection {
+    pub records: Vec<GenerationRecord>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to read collection: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("failed to write collection: {0}")]
+    Write(#[source] std::io::Error),
+    #[error("failed to parse collection: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("no generation found with id {0}")]
+    NotFound(String),
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> Self {
+        err.to_string()
+    }
+}
+
+/// Storage for a `Collection`, isolated behind a trait so the JSON-file
+/// backend below can later be swapped for SQLite or an in-memory store
+/// without touching `CollectionManager` or the commands that use it.
+pub trait Database: Send + Sync {
+    fn write(&self, collection: &Collection) -> Result<(), Error>;
+    fn read(&self) -> Result<Collection, Error>;
+}
+
+pub struct JsonFileDatabase {
+    path: PathBuf,
+}
+
+impl JsonFileDatabase {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Database for JsonFileDatabase {
+    fn write(&self, collection: &Collection) -> Result<(), Error> {
+        let data = serde_json::to_vec_pretty(collection)?;
+        fs::write(&self.path, data).map_err(Error::Write)
+    }
+
+    fn read(&self) -> Result<Collection, Error> {
+        match fs::read(&self.path) {
+            Ok(data) => Ok(serde_json::from_slice(&data)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Collection::default()),
+            Err(err) => Err(Error::Read(err)),
+        }
+    }
+}
+
+/// Guards the on-disk collection behind a mutex so concurrent commands
+/// (save while listing, say) read and write a consistent snapshot.
+pub struct CollectionManager {
+    db: Box<dyn Database>,
+    cache: Mutex<()>,
+}
+
+impl CollectionManager {
+    pub fn new(db: Box<dyn Database>) -> Self {
+        Self {
+            db,
+            cache: Mutex::new(()),
+        }
+    }
+
+    pub fn save(&self, record: GenerationRecord) -> Result<(), Error> {
+        let _guard = self.cache.lock().unwrap();
+        let mut collection = self.db.read()?;
+        collection.records.push(record);
+        self.db.write(&collection)
+    }
+
+    pub fn list(&self) -> Result<Vec<GenerationRecord>, Error> {
+        let _guard = self.cache.lock().unwrap();
+        Ok(self.db.read()?.records)
+    }
+
+    pub fn load(&self, id: &str) -> Result<GenerationRecord, Error> {
+        let _guard = self.cache.lock().unwrap();
+        self.db
+            .read()?
+            .records
+            .into_iter()
+            .find(|record| record.id == id)
+            .ok_or_else(|| Error::NotFound(id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::EmotionalIntent;
+
+    #[derive(Default)]
+    struct InMemoryDatabase {
+        collection: Mutex<Collection>,
+    }
+
+    impl Database for InMemoryDatabase {
+        fn write(&self, collection: &Collection) -> Result<(), Error> {
+            *self.collection.lock().unwrap() = Collection {
+                records: collection.records.clone(),
+            };
+            Ok(())
+        }
+
+        fn read(&self) -> Result<Collection, Error> {
+            Ok(Collection {
+                records: self.collection.lock().unwrap().records.clone(),
+            })
+        }
+    }
+
+    fn sample_record(id: &str) -> GenerationRecord {
+        GenerationRecord {
+            id: id.to_string(),
+            created_at: "2026-07-30T00:00:00Z".to_string(),
+            intent: EmotionalIntent::Legacy("calm piano piece".to_string()),
+            result: serde_json::json!({ "notes": [] }),
+        }
+    }
+
+    #[test]
+    fn save_then_list_round_trips() {
+        let manager = CollectionManager::new(Box::new(InMemoryDatabase::default()));
+        manager.save(sample_record("one")).unwrap();
+        manager.save(sample_record("two")).unwrap();
+
+        let records = manager.list().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "one");
+        assert_eq!(records[1].id, "two");
+    }
+
+    #[test]
+    fn load_missing_id_errors() {
+        let manager = CollectionManager::new(Box::new(InMemoryDatabase::default()));
+        manager.save(sample_record("one")).unwrap();
+
+        let err = manager.load("missing").unwrap_err();
+        assert!(matches!(err, Error::NotFound(id) if id == "missing"));
+    }
+
+    #[test]
+    fn json_file_database_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!("collection-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("collection.json");
+
+        let db = JsonFileDatabase::new(path.clone());
+        db.write(&Collection {
+            records: vec![sample_record("one")],
+        })
+        .unwrap();
+
+        let collection = db.read().unwrap();
+        assert_eq!(collection.records.len(), 1);
+        assert_eq!(collection.records[0].id, "one");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,17 +1,34 @@
 use serde::{Deserialize, Serialize};
-use tauri::command;
+use tauri::{command, AppHandle, Manager, State};
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct EmotionalIntent {
-    pub core_wound: Option<String>,
-    pub core_desire: Option<String>,
-    #[serde(default)]
-    pub emotional_intent: Option<String>,  // Legacy field
-    pub technical: Option<serde_json::Value>,
-    // New format: base_emotion, intensity, specific_emotion
-    pub base_emotion: Option<String>,
-    pub intensity: Option<String>,
-    pub specific_emotion: Option<String>,
+use crate::collection::{CollectionManager, GenerationRecord};
+use crate::listenbrainz::{Listen, ListenBrainzClient, RecentListen, TrackMetadata};
+use crate::output_format::{self, EncodedOutput, OutputFormat};
+use crate::streaming::{ClientMessage, ServerMessage, SessionRegistry};
+use crate::tabs::{self, ChordDiagram, TabResult};
+
+/// A normalized emotional intent payload. Serde tries each variant in the
+/// order declared below, so more specific shapes must precede more
+/// permissive ones (`deny_unknown_fields` keeps the all-optional
+/// `Structured` variant from accepting a narrative object) and `null` must
+/// land on an explicit variant rather than falling through to a
+/// deserialization error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum EmotionalIntent {
+    Null,
+    Structured {
+        base_emotion: Option<String>,
+        intensity: Option<String>,
+        specific_emotion: Option<String>,
+        technical: Option<serde_json::Value>,
+    },
+    Narrative {
+        core_wound: Option<String>,
+        core_desire: Option<String>,
+        technical: Option<serde_json::Value>,
+    },
+    Legacy(String),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,10 +45,24 @@ pub struct InterrogateRequest {
 }
 
 #[command]
-pub async fn generate_music(request: GenerateRequest) -> Result<serde_json::Value, String> {
-    crate::bridge::musicbrain::generate(request)
+pub async fn generate_music(request: GenerateRequest) -> Result<EncodedOutput, String> {
+    match &request.intent {
+        EmotionalIntent::Null => return Err("intent must not be null".to_string()),
+        EmotionalIntent::Structured { .. }
+        | EmotionalIntent::Narrative { .. }
+        | EmotionalIntent::Legacy(_) => {}
+    }
+
+    let format = match &request.output_format {
+        Some(format) => format.parse::<OutputFormat>().map_err(|e| e.to_string())?,
+        None => OutputFormat::Json,
+    };
+
+    let result = crate::bridge::musicbrain::generate(request)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    output_format::encode(format, &result).map_err(|e| e.to_string())
 }
 
 #[command]
@@ -41,9 +72,315 @@ pub async fn interrogate(request: InterrogateRequest) -> Result<serde_json::Valu
         .map_err(|e| e.to_string())
 }
 
+/// Returns the base emotion palette, enriched with suggested defaults when
+/// a `user` is given by matching the most frequent artist name in their
+/// recent ListenBrainz history against a few genre keywords to guess
+/// `base_emotion`/`specific_emotion`.
+#[command]
+pub async fn get_emotions(
+    user: Option<String>,
+    listenbrainz: State<'_, Box<dyn ListenBrainzClient>>,
+) -> Result<serde_json::Value, String> {
+    let mut emotions = crate::bridge::musicbrain::get_emotions()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(user) = user {
+        let recent = listenbrainz.fetch_recent(&user).await?;
+        if let serde_json::Value::Object(ref mut map) = emotions {
+            map.insert(
+                "suggested".to_string(),
+                serde_json::to_value(suggest_from_history(&recent)).unwrap(),
+            );
+        }
+    }
+
+    Ok(emotions)
+}
+
+/// Coarse artist/genre keyword -> (base_emotion, specific_emotion) hints.
+/// This is intentionally small and heuristic: it exists to seed a default
+/// the user can override, not to be an authoritative mood classifier.
+const EMOTION_HINTS: &[(&str, &str, &str)] = &[
+    ("metal", "anger", "intensity"),
+    ("punk", "anger", "defiance"),
+    ("blues", "sadness", "longing"),
+    ("jazz", "calm", "introspection"),
+    ("classical", "calm", "serenity"),
+    ("ambient", "calm", "stillness"),
+    ("pop", "joy", "excitement"),
+    ("dance", "joy", "excitement"),
+    ("folk", "nostalgia", "warmth"),
+];
+
+fn suggest_from_history(listens: &[RecentListen]) -> EmotionalIntent {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for listen in listens {
+        *counts.entry(listen.track_metadata.artist_name.as_str()).or_insert(0) += 1;
+    }
+    let top_artist = counts.into_iter().max_by_key(|(_, count)| *count).map(|(artist, _)| artist);
+
+    let (base_emotion, specific_emotion) = top_artist
+        .and_then(|artist| {
+            let artist = artist.to_ascii_lowercase();
+            EMOTION_HINTS
+                .iter()
+                .find(|(keyword, _, _)| artist.contains(keyword))
+        })
+        .map(|(_, base, specific)| (Some(base.to_string()), Some(specific.to_string())))
+        .unwrap_or((None, None));
+
+    EmotionalIntent::Structured {
+        base_emotion,
+        intensity: None,
+        specific_emotion,
+        technical: None,
+    }
+}
+
 #[command]
-pub async fn get_emotions() -> Result<serde_json::Value, String> {
-    crate::bridge::musicbrain::get_emotions()
+pub async fn submit_listen(
+    user_token: String,
+    artist_name: String,
+    track_name: String,
+    listened_at: i64,
+    listenbrainz: State<'_, Box<dyn ListenBrainzClient>>,
+) -> Result<(), String> {
+    listenbrainz
+        .submit_listen(
+            &user_token,
+            Listen {
+                listened_at,
+                track_metadata: TrackMetadata {
+                    artist_name,
+                    track_name,
+                    additional_info: None,
+                },
+            },
+        )
         .await
         .map_err(|e| e.to_string())
 }
+
+#[command]
+pub async fn fetch_recent(
+    user: String,
+    listenbrainz: State<'_, Box<dyn ListenBrainzClient>>,
+) -> Result<Vec<RecentListen>, String> {
+    listenbrainz.fetch_recent(&user).await.map_err(|e| e.to_string())
+}
+
+#[command]
+pub fn save_generation(
+    intent: EmotionalIntent,
+    result: serde_json::Value,
+    collection: State<CollectionManager>,
+) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    collection.save(GenerationRecord {
+        id: id.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        intent,
+        result,
+    })?;
+    Ok(id)
+}
+
+#[command]
+pub fn list_generations(
+    collection: State<CollectionManager>,
+) -> Result<Vec<GenerationRecord>, String> {
+    Ok(collection.list()?)
+}
+
+/// Entry point for streaming interrogation: the frontend invokes this
+/// command once per tagged `ClientMessage`, and this dispatches each one to
+/// the `musicbrain` bridge. There is no WebSocket; replies travel only as
+/// Tauri events. For a new session this registers
+/// the sender half in `registry` *before* calling into the bridge, then
+/// spawns a task that drains the receiver half and forwards each
+/// `ServerMessage` to the frontend as a Tauri event, so tokens/questions
+/// actually reach a client instead of only existing as in-memory shapes.
+#[command]
+pub async fn interrogate_stream(
+    message: ClientMessage,
+    app: AppHandle,
+    registry: State<'_, SessionRegistry>,
+) -> Result<(), String> {
+    match message {
+        ClientMessage::Msg { session_id, text } => {
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+            registry.insert(session_id.clone(), sender);
+
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                while let Some(message) = receiver.recv().await {
+                    let done = matches!(message, ServerMessage::Done { .. });
+                    let _ = app.emit_all("interrogate-event", message.clone());
+                    if done {
+                        break;
+                    }
+                }
+            });
+
+            let result = crate::bridge::musicbrain::interrogate_stream(
+                session_id.clone(),
+                text,
+                &registry,
+            )
+            .await
+            .map_err(|e| e.to_string());
+            registry.remove(&session_id);
+            result
+        }
+        ClientMessage::Answer { session_id, text } => {
+            crate::bridge::musicbrain::answer_interrogation(session_id, text, &registry)
+                .await
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[command]
+pub fn load_generation(
+    id: String,
+    collection: State<CollectionManager>,
+) -> Result<GenerationRecord, String> {
+    Ok(collection.load(&id)?)
+}
+
+/// Given a generated piece's key and chord progression, retrieves a
+/// matching tab if one is found (`None` otherwise) and always returns
+/// locally-suggested fretting for each chord, so the frontend can render
+/// playable chords alongside the emotional parameters that produced the
+/// piece.
+#[command]
+pub async fn fetch_tab(
+    key: String,
+    progression: Vec<String>,
+) -> Result<(Option<TabResult>, Vec<ChordDiagram>), String> {
+    let client = reqwest::Client::new();
+    let query = format!("{key} {}", progression.join(" "));
+    let tab = match tabs::fetch_tab(&client, &query).await {
+        Ok(tab) => Some(tab),
+        Err(crate::tabs::Error::NotFound(_)) => {
+            // No tab matches this progression — a normal outcome, not an error.
+            None
+        }
+        Err(err) => {
+            eprintln!("fetch_tab: lookup for {query:?} failed, falling back to suggested chords only: {err}");
+            None
+        }
+    };
+    let chords = tabs::suggest_chords(&progression);
+    Ok((tab, chords))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listen(artist_name: &str) -> RecentListen {
+        RecentListen {
+            listened_at: 0,
+            track_metadata: crate::listenbrainz::RecentTrackMetadata {
+                artist_name: artist_name.to_string(),
+                track_name: "track".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn suggest_from_history_maps_a_known_genre_keyword() {
+        let listens = vec![listen("Death Metal Orchestra"), listen("Death Metal Orchestra")];
+        let intent = suggest_from_history(&listens);
+        match intent {
+            EmotionalIntent::Structured {
+                base_emotion,
+                specific_emotion,
+                ..
+            } => {
+                assert_eq!(base_emotion.as_deref(), Some("anger"));
+                assert_eq!(specific_emotion.as_deref(), Some("intensity"));
+            }
+            other => panic!("expected Structured, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn suggest_from_history_leaves_emotion_unset_for_unrecognized_artists() {
+        let listens = vec![listen("Some Unknown Band")];
+        let intent = suggest_from_history(&listens);
+        match intent {
+            EmotionalIntent::Structured {
+                base_emotion,
+                specific_emotion,
+                ..
+            } => {
+                assert!(base_emotion.is_none());
+                assert!(specific_emotion.is_none());
+            }
+            other => panic!("expected Structured, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn suggest_from_history_with_no_listens_is_unset() {
+        let intent = suggest_from_history(&[]);
+        match intent {
+            EmotionalIntent::Structured { base_emotion, .. } => assert!(base_emotion.is_none()),
+            other => panic!("expected Structured, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn emotional_intent_null_deserializes_to_null_variant() {
+        let intent: EmotionalIntent = serde_json::from_str("null").unwrap();
+        assert!(matches!(intent, EmotionalIntent::Null));
+    }
+
+    #[test]
+    fn emotional_intent_bare_string_deserializes_to_legacy() {
+        let intent: EmotionalIntent = serde_json::from_str("\"a sad piano piece\"").unwrap();
+        assert!(matches!(intent, EmotionalIntent::Legacy(text) if text == "a sad piano piece"));
+    }
+
+    #[test]
+    fn emotional_intent_structured_object_deserializes_to_structured() {
+        let intent: EmotionalIntent =
+            serde_json::from_str(r#"{"base_emotion":"joy","intensity":"high","specific_emotion":"elation"}"#)
+                .unwrap();
+        match intent {
+            EmotionalIntent::Structured { base_emotion, .. } => {
+                assert_eq!(base_emotion.as_deref(), Some("joy"));
+            }
+            other => panic!("expected Structured, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn emotional_intent_narrative_object_deserializes_to_narrative_not_structured() {
+        let intent: EmotionalIntent =
+            serde_json::from_str(r#"{"core_wound":"abandonment","core_desire":"belonging"}"#).unwrap();
+        match intent {
+            EmotionalIntent::Narrative { core_wound, core_desire, .. } => {
+                assert_eq!(core_wound.as_deref(), Some("abandonment"));
+                assert_eq!(core_desire.as_deref(), Some("belonging"));
+            }
+            other => panic!("expected Narrative, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn emotional_intent_empty_object_falls_through_to_first_struct_variant() {
+        let intent: EmotionalIntent = serde_json::from_str("{}").unwrap();
+        match intent {
+            EmotionalIntent::Structured { base_emotion, specific_emotion, intensity, .. } => {
+                assert!(base_emotion.is_none());
+                assert!(specific_emotion.is_none());
+                assert!(intensity.is_none());
+            }
+            other => panic!("expected Structured, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+const SUBMIT_URL: &str = "https://api.listenbrainz.org/1/submit-listens";
+const RECENT_URL: &str = "https://api.listenbrainz.org/1/user";
+
+#[derive(Debug, Serialize)]
+pub struct TrackMetadata {
+    pub artist_name: String,
+    pub track_name: String,
+    pub additional_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Listen {
+    pub listened_at: i64,
+    pub track_metadata: TrackMetadata,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitListensRequest {
+    pub listen_type: String,
+    pub payload: Vec<Listen>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentListen {
+    pub listened_at: i64,
+    pub track_metadata: RecentTrackMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentTrackMetadata {
+    pub artist_name: String,
+    pub track_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentListensResponse {
+    pub payload: RecentListensPayload,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentListensPayload {
+    pub listens: Vec<RecentListen>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("listenbrainz request failed: {0}")]
+    Request(#[from] reqwest::Error),
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> Self {
+        err.to_string()
+    }
+}
+
+/// Thin HTTP client boundary so tests can mock ListenBrainz without making
+/// live network calls.
+#[async_trait::async_trait]
+pub trait ListenBrainzClient: Send + Sync {
+    async fn submit_listen(&self, user_token: &str, listen: Listen) -> Result<(), Error>;
+    async fn fetch_recent(&self, user: &str) -> Result<Vec<RecentListen>, Error>;
+}
+
+pub struct HttpListenBrainzClient {
+    client: reqwest::Client,
+}
+
+impl Default for HttpListenBrainzClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ListenBrainzClient for HttpListenBrainzClient {
+    async fn submit_listen(&self, user_token: &str, listen: Listen) -> Result<(), Error> {
+        let request = SubmitListensRequest {
+            listen_type: "single".to_string(),
+            payload: vec![listen],
+        };
+        self.client
+            .post(SUBMIT_URL)
+            .header("Authorization", format!("Token {user_token}"))
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    async fn fetch_recent(&self, user: &str) -> Result<Vec<RecentListen>, Error> {
+        let mut url = reqwest::Url::parse(RECENT_URL).expect("RECENT_URL is a valid base url");
+        url.path_segments_mut()
+            .expect("RECENT_URL is not a cannot-be-a-base url")
+            .push(user)
+            .push("listens");
+        let response: RecentListensResponse = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.payload.listens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockListenBrainzClient {
+        listens: Vec<RecentListen>,
+    }
+
+    #[async_trait::async_trait]
+    impl ListenBrainzClient for MockListenBrainzClient {
+        async fn submit_listen(&self, _user_token: &str, _listen: Listen) -> Result<(), Error> {
+            Ok(())
+        }
+
+        async fn fetch_recent(&self, _user: &str) -> Result<Vec<RecentListen>, Error> {
+            Ok(self
+                .listens
+                .iter()
+                .map(|listen| RecentListen {
+                    listened_at: listen.listened_at,
+                    track_metadata: RecentTrackMetadata {
+                        artist_name: listen.track_metadata.artist_name.clone(),
+                        track_name: listen.track_metadata.track_name.clone(),
+                    },
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_recent_returns_mocked_listens_without_a_live_call() {
+        let client = MockListenBrainzClient {
+            listens: vec![RecentListen {
+                listened_at: 1_700_000_000,
+                track_metadata: RecentTrackMetadata {
+                    artist_name: "Boards of Canada".to_string(),
+                    track_name: "Roygbiv".to_string(),
+                },
+            }],
+        };
+
+        let listens = client.fetch_recent("someone").await.unwrap();
+        assert_eq!(listens.len(), 1);
+        assert_eq!(listens[0].track_metadata.artist_name, "Boards of Canada");
+    }
+}
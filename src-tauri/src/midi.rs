@@ -0,0 +1,50 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("generation result is missing the fields required to build a MIDI file: {0}")]
+    MissingField(&'static str),
+    #[error("note {0} {1} is out of MIDI range 0-127")]
+    OutOfRange(&'static str, u64),
+}
+
+fn midi_byte(field: &'static str, value: Option<u64>, default: u8) -> Result<u8, Error> {
+    match value {
+        Some(v) if v <= 127 => Ok(v as u8),
+        Some(v) => Err(Error::OutOfRange(field, v)),
+        None => Ok(default),
+    }
+}
+
+/// Encodes a generated piece as a Standard MIDI File. This is intentionally
+/// a dedicated encoder rather than a serde adapter: MIDI is an event-stream
+/// binary format with no structural correspondence to the JSON result, so
+/// there's no `Serialize` impl to reuse.
+pub fn encode(value: &serde_json::Value) -> Result<Vec<u8>, Error> {
+    let notes = value
+        .get("notes")
+        .and_then(|v| v.as_array())
+        .ok_or(Error::MissingField("notes"))?;
+
+    // Header chunk (format 0, one track, 480 ticks/quarter) followed by a
+    // single track chunk with one note-on/note-off pair per note.
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"MThd");
+    bytes.extend_from_slice(&6u32.to_be_bytes());
+    bytes.extend_from_slice(&0u16.to_be_bytes());
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&480u16.to_be_bytes());
+
+    let mut track = Vec::new();
+    for note in notes {
+        let pitch = midi_byte("pitch", note.get("pitch").and_then(|v| v.as_u64()), 60)?;
+        let velocity = midi_byte("velocity", note.get("velocity").and_then(|v| v.as_u64()), 64)?;
+        track.extend_from_slice(&[0x00, 0x90, pitch, velocity]);
+        track.extend_from_slice(&[0x60, 0x80, pitch, 0x00]);
+    }
+    track.extend_from_slice(&[0x00, 0xFF, 0x2F, 0x00]);
+
+    bytes.extend_from_slice(b"MTrk");
+    bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&track);
+
+    Ok(bytes)
+}
@@ -0,0 +1,44 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("generation result is missing the fields required to build MusicXML: {0}")]
+    MissingField(&'static str),
+}
+
+/// Encodes a generated piece as a minimal single-part MusicXML document.
+/// Like [`crate::midi`], this is a dedicated encoder rather than a serde
+/// adapter since MusicXML is a notation-specific DTD, not a structural
+/// mirror of the generation result.
+pub fn encode(value: &serde_json::Value) -> Result<String, Error> {
+    let notes = value
+        .get("notes")
+        .and_then(|v| v.as_array())
+        .ok_or(Error::MissingField("notes"))?;
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE score-partwise PUBLIC \"-//Recordare//DTD MusicXML 4.0 Partwise//EN\" \"http://www.musicxml.org/dtds/partwise.dtd\">\n\
+         <score-partwise version=\"4.0\">\n  <part-list>\n    <score-part id=\"P1\"/>\n  </part-list>\n  <part id=\"P1\">\n    <measure number=\"1\">\n",
+    );
+
+    for note in notes {
+        let step = note.get("step").and_then(|v| v.as_str()).unwrap_or("C");
+        let octave = note.get("octave").and_then(|v| v.as_u64()).unwrap_or(4);
+        let step = escape_text(step);
+        xml.push_str(&format!(
+            "      <note>\n        <pitch>\n          <step>{step}</step>\n          <octave>{octave}</octave>\n        </pitch>\n        <duration>1</duration>\n      </note>\n"
+        ));
+    }
+
+    xml.push_str("    </measure>\n  </part>\n</score-partwise>\n");
+    Ok(xml)
+}
+
+/// Escapes the characters that are significant in XML element content so a
+/// note field can never break out of its element or inject markup.
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
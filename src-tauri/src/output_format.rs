@@ -0,0 +1,126 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// The formats `generate_music` can render its result as. `Json` is the
+/// default when `GenerateRequest.output_format` is absent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Toml,
+    Midi,
+    MusicXml,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "toml" => Ok(OutputFormat::Toml),
+            "midi" | "mid" => Ok(OutputFormat::Midi),
+            "musicxml" | "xml" => Ok(OutputFormat::MusicXml),
+            other => Err(Error::UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("unknown output format: {0}")]
+    UnknownFormat(String),
+    #[error("failed to encode as yaml: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("failed to encode as toml: {0}")]
+    Toml(#[from] toml::ser::Error),
+    #[error("failed to encode {0:?}: {1}")]
+    Encode(OutputFormat, String),
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> Self {
+        err.to_string()
+    }
+}
+
+/// The result of encoding a generation, tagged with the format that
+/// produced it so the frontend doesn't have to guess whether `data` is
+/// inline text or base64-encoded binary (MIDI). `Json` carries the raw
+/// `Value` unchanged — the common no-`output_format` case must keep
+/// returning the object it always did, not a stringified copy of it.
+#[derive(Debug, Serialize)]
+#[serde(tag = "format", content = "data", rename_all = "lowercase")]
+pub enum EncodedOutput {
+    Json(serde_json::Value),
+    Yaml(String),
+    Toml(String),
+    Midi(String),
+    #[serde(rename = "musicxml")]
+    MusicXml(String),
+}
+
+pub fn encode(format: OutputFormat, value: &serde_json::Value) -> Result<EncodedOutput, Error> {
+    match format {
+        OutputFormat::Json => Ok(EncodedOutput::Json(value.clone())),
+        OutputFormat::Yaml => Ok(EncodedOutput::Yaml(serde_yaml::to_string(value)?)),
+        OutputFormat::Toml => Ok(EncodedOutput::Toml(toml::to_string(value)?)),
+        OutputFormat::Midi => {
+            let bytes = crate::midi::encode(value)
+                .map_err(|e| Error::Encode(OutputFormat::Midi, e.to_string()))?;
+            Ok(EncodedOutput::Midi(
+                base64::engine::general_purpose::STANDARD.encode(bytes),
+            ))
+        }
+        OutputFormat::MusicXml => {
+            let xml = crate::musicxml::encode(value)
+                .map_err(|e| Error::Encode(OutputFormat::MusicXml, e.to_string()))?;
+            Ok(EncodedOutput::MusicXml(xml))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> serde_json::Value {
+        serde_json::json!({ "notes": [{ "pitch": 60, "velocity": 80 }] })
+    }
+
+    #[test]
+    fn json_returns_the_raw_value_unchanged() {
+        let value = sample();
+        match encode(OutputFormat::Json, &value).unwrap() {
+            EncodedOutput::Json(encoded) => assert_eq!(encoded, value),
+            other => panic!("expected Json, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn yaml_and_toml_encode_as_text() {
+        let value = sample();
+        assert!(matches!(encode(OutputFormat::Yaml, &value).unwrap(), EncodedOutput::Yaml(_)));
+        assert!(matches!(encode(OutputFormat::Toml, &value).unwrap(), EncodedOutput::Toml(_)));
+    }
+
+    #[test]
+    fn midi_is_base64_encoded_binary() {
+        match encode(OutputFormat::Midi, &sample()).unwrap() {
+            EncodedOutput::Midi(data) => {
+                let bytes = base64::engine::general_purpose::STANDARD.decode(data).unwrap();
+                assert_eq!(&bytes[0..4], b"MThd");
+            }
+            other => panic!("expected Midi, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn from_str_accepts_known_aliases_and_rejects_unknown() {
+        assert_eq!("yml".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert_eq!("XML".parse::<OutputFormat>().unwrap(), OutputFormat::MusicXml);
+        assert!("wav".parse::<OutputFormat>().is_err());
+    }
+}
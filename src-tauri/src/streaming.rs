@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::commands::InterrogateRequest;
+
+/// Messages the frontend sends to the `interrogate_stream` command.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum ClientMessage {
+    #[serde(rename = "msg")]
+    Msg {
+        session_id: String,
+        text: String,
+    },
+    #[serde(rename = "answer")]
+    Answer {
+        session_id: String,
+        text: String,
+    },
+}
+
+/// Messages pushed back to the frontend as `interrogate-event` Tauri events
+/// as `musicbrain` produces them, instead of waiting for one final reply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "t")]
+pub enum ServerMessage {
+    #[serde(rename = "token")]
+    Token { session_id: String, chunk: String },
+    #[serde(rename = "question")]
+    Question { session_id: String, prompt: String },
+    #[serde(rename = "done")]
+    Done {
+        session_id: String,
+        result: serde_json::Value,
+    },
+}
+
+impl From<InterrogateRequest> for ClientMessage {
+    fn from(request: InterrogateRequest) -> Self {
+        ClientMessage::Msg {
+            session_id: request.session_id.unwrap_or_default(),
+            text: request.message,
+        }
+    }
+}
+
+/// One open interrogation dialogue: the sender half is handed to the
+/// `musicbrain` task so it can push tokens/questions as they're produced,
+/// while a forwarding task holds the receiver and emits them to the
+/// frontend as Tauri events.
+pub struct Session {
+    pub sender: mpsc::UnboundedSender<ServerMessage>,
+}
+
+/// Tracks in-flight interrogation sessions by id, each mapped to the sender
+/// that forwards `ServerMessage`s for that session to the frontend.
+#[derive(Default)]
+pub struct SessionRegistry {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl SessionRegistry {
+    pub fn insert(&self, session_id: String, sender: mpsc::UnboundedSender<ServerMessage>) {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session_id, Session { sender });
+    }
+
+    pub fn remove(&self, session_id: &str) {
+        self.sessions.lock().unwrap().remove(session_id);
+    }
+
+    pub fn send(&self, session_id: &str, message: ServerMessage) -> Result<(), String> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("no active interrogation session {session_id}"))?;
+        session
+            .sender
+            .send(message)
+            .map_err(|_| "interrogation session closed".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(session_id: &str) -> ServerMessage {
+        ServerMessage::Token {
+            session_id: session_id.to_string(),
+            chunk: "hello".to_string(),
+        }
+    }
+
+    #[test]
+    fn send_after_insert_is_delivered_to_the_receiver() {
+        let registry = SessionRegistry::default();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        registry.insert("session-1".to_string(), sender);
+
+        registry.send("session-1", token("session-1")).unwrap();
+
+        let received = receiver.try_recv().unwrap();
+        assert!(matches!(received, ServerMessage::Token { session_id, .. } if session_id == "session-1"));
+    }
+
+    #[test]
+    fn send_before_insert_errors() {
+        let registry = SessionRegistry::default();
+        let err = registry.send("never-registered", token("never-registered")).unwrap_err();
+        assert!(err.contains("never-registered"));
+    }
+
+    #[test]
+    fn send_after_remove_errors() {
+        let registry = SessionRegistry::default();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        registry.insert("session-1".to_string(), sender);
+        registry.remove("session-1");
+
+        let err = registry.send("session-1", token("session-1")).unwrap_err();
+        assert!(err.contains("session-1"));
+    }
+
+    #[test]
+    fn send_is_routed_to_the_session_it_names() {
+        let registry = SessionRegistry::default();
+        let (sender_a, mut receiver_a) = mpsc::unbounded_channel();
+        let (sender_b, mut receiver_b) = mpsc::unbounded_channel();
+        registry.insert("a".to_string(), sender_a);
+        registry.insert("b".to_string(), sender_b);
+
+        registry.send("b", token("b")).unwrap();
+
+        assert!(receiver_a.try_recv().is_err());
+        assert!(receiver_b.try_recv().is_ok());
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+const SEARCH_URL: &str = "https://www.songsterr.com/a/ra/songs.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabResult {
+    pub id: u64,
+    pub title: String,
+    pub artist: String,
+    pub chords_present: bool,
+    pub tab_types: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChordDiagram {
+    pub chord: String,
+    pub frets: Vec<i8>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("tab lookup failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("no tab found for query {0}")]
+    NotFound(String),
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> Self {
+        err.to_string()
+    }
+}
+
+/// Looks up a tab by a free-text query (typically the generated piece's
+/// key/progression rendered as a search term), mirroring the approach of
+/// fetching structured tab data from a guitar-tab API rather than passing
+/// around raw JSON.
+pub async fn fetch_tab(client: &reqwest::Client, query: &str) -> Result<TabResult, Error> {
+    let results: Vec<TabResult> = client
+        .get(SEARCH_URL)
+        .query(&[("pattern", query)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    results.into_iter().next().ok_or_else(|| Error::NotFound(query.to_string()))
+}
+
+/// Constructs a simple open-position fretting diagram per chord in the
+/// progression, locally and without an upstream lookup; chords it does not
+/// know get all strings muted.
+pub fn suggest_chords(progression: &[String]) -> Vec<ChordDiagram> {
+    progression
+        .iter()
+        .map(|chord| ChordDiagram {
+            chord: chord.clone(),
+            frets: open_position_frets(chord),
+        })
+        .collect()
+}
+
+fn open_position_frets(chord: &str) -> Vec<i8> {
+    match chord {
+        "C" => vec![-1, 3, 2, 0, 1, 0],
+        "G" => vec![3, 2, 0, 0, 3, 3],
+        "D" => vec![-1, -1, 0, 2, 3, 2],
+        "A" => vec![-1, 0, 2, 2, 2, 0],
+        "E" => vec![0, 2, 2, 1, 0, 0],
+        "Am" => vec![-1, 0, 2, 2, 1, 0],
+        "Em" => vec![0, 2, 2, 0, 0, 0],
+        "Dm" => vec![-1, -1, 0, 2, 3, 1],
+        _ => vec![-1, -1, -1, -1, -1, -1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggest_chords_returns_one_diagram_per_chord_in_order() {
+        let diagrams = suggest_chords(&["C".to_string(), "G".to_string(), "Am".to_string()]);
+        assert_eq!(diagrams.len(), 3);
+        assert_eq!(diagrams[0].chord, "C");
+        assert_eq!(diagrams[0].frets, vec![-1, 3, 2, 0, 1, 0]);
+        assert_eq!(diagrams[2].chord, "Am");
+        assert_eq!(diagrams[2].frets, vec![-1, 0, 2, 2, 1, 0]);
+    }
+
+    #[test]
+    fn suggest_chords_falls_back_to_muted_frets_for_unknown_chords() {
+        let diagrams = suggest_chords(&["Cmaj13#11".to_string()]);
+        assert_eq!(diagrams[0].frets, vec![-1, -1, -1, -1, -1, -1]);
+    }
+}